@@ -0,0 +1,189 @@
+// Import the frozen forecaster this agent builds its state around
+use crate::data;
+use crate::model::{GasPriceNet, ModelConfig};
+// Import PyTorch components for the policy network and its training loop
+use tch::{nn, nn::Module, nn::OptimizerConfig, Device, Kind, Tensor};
+
+// Large penalty (gwei-equivalent) charged if the agent never submits before the deadline
+const DEADLINE_PENALTY: f64 = 500.0;
+
+// Hyperparameters for the timing policy and its actor-critic training loop
+pub struct AgentConfig {
+    // Width of the shared trunk between the policy and value heads
+    pub hidden_size: i64,
+    // Adam learning rate
+    pub learning_rate: f64,
+    // Number of blocks the agent has to submit before the deadline penalty applies
+    pub deadline_blocks: usize,
+    // Weight on the entropy bonus, encourages exploration early in training
+    pub entropy_weight: f64,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            hidden_size: 32,
+            learning_rate: 1e-3,
+            deadline_blocks: 10,
+            entropy_weight: 0.01,
+        }
+    }
+}
+
+// Action the agent can take on each block: submit the transaction now, or
+// wait and see if a cheaper block comes along before the deadline
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Submit,
+    Wait,
+}
+
+// Small actor-critic network: a shared trunk feeding a 2-way policy head
+// (submit/wait logits) and a scalar value head, the standard
+// feature-extraction-then-RL shape used for trading/timing agents
+pub struct TimingPolicy {
+    trunk: nn::Linear,
+    policy_head: nn::Linear,
+    value_head: nn::Linear,
+}
+
+impl TimingPolicy {
+    // Constructor to create a new policy instance
+    // `input_size` is the frozen forecaster's feature width plus its forecast
+    pub fn new(vs: &nn::Path, input_size: i64, config: &AgentConfig) -> Self {
+        let trunk = nn::linear(vs / "trunk", input_size, config.hidden_size, Default::default());
+        let policy_head = nn::linear(vs / "policy_head", config.hidden_size, 2, Default::default());
+        let value_head = nn::linear(vs / "value_head", config.hidden_size, 1, Default::default());
+        Self {
+            trunk,
+            policy_head,
+            value_head,
+        }
+    }
+
+    // Forward pass: returns (action logits, state value) for a batch of states
+    fn forward(&self, xs: &Tensor) -> (Tensor, Tensor) {
+        let hidden = xs.apply(&self.trunk).relu();
+        (hidden.apply(&self.policy_head), hidden.apply(&self.value_head))
+    }
+}
+
+// One step of rollout, kept around long enough to compute the episode's loss
+struct StepOutcome {
+    log_prob: Tensor,
+    value: Tensor,
+    entropy: Tensor,
+}
+
+// Build the state vector for one block: its 7 raw features plus the frozen
+// forecaster's predicted price, so the policy sees both the market and the
+// model's own forecast of where it's heading
+fn build_state(features: &[f64; 7], forecaster: &GasPriceNet, device: Device) -> Tensor {
+    let input = Tensor::of_slice(features).to_device(device).unsqueeze(0);
+    let forecast = tch::no_grad(|| forecaster.forward(&input));
+    Tensor::cat(&[input, forecast], 1)
+}
+
+// Run a single episode: walk a simulated block series, sampling submit/wait
+// from the policy at each block, until the agent submits or the deadline
+// (`config.deadline_blocks`) is reached
+// Returns the rollout's per-step outcomes and the final (negative-cost) reward
+fn run_episode(
+    policy: &TimingPolicy,
+    forecaster: &GasPriceNet,
+    device: Device,
+    config: &AgentConfig,
+) -> (Vec<StepOutcome>, f64) {
+    // Reuse the data generator's ordered block series for one simulated episode
+    let (blocks, prices) = data::generate_gas_block_series(config.deadline_blocks);
+
+    let mut outcomes = Vec::with_capacity(blocks.len());
+
+    for (t, block_features) in blocks.iter().enumerate() {
+        let state = build_state(block_features, forecaster, device);
+        let (logits, value) = policy.forward(&state);
+
+        let log_probs = logits.log_softmax(-1, Kind::Float);
+        let probs = log_probs.exp();
+        let entropy = -(&probs * &log_probs).sum(Kind::Float);
+
+        // Sample an action from the policy's current distribution
+        let action_index = i64::from(probs.multinomial(1, true));
+        let action = if action_index == 0 { Action::Submit } else { Action::Wait };
+        let log_prob = log_probs.narrow(1, action_index, 1).squeeze();
+
+        outcomes.push(StepOutcome {
+            log_prob,
+            value: value.squeeze(),
+            entropy,
+        });
+
+        let is_last_block = t == blocks.len() - 1;
+        if action == Action::Submit || is_last_block {
+            // Reward is negative the gas actually paid; missing the deadline
+            // (waiting through the last block) adds a large extra penalty
+            let reward = if action == Action::Submit {
+                -prices[t]
+            } else {
+                -(prices[t] + DEADLINE_PENALTY)
+            };
+            return (outcomes, reward);
+        }
+    }
+
+    unreachable!("loop always returns by the last block")
+}
+
+// Train the timing policy with a one-step actor-critic update per episode
+// Every step in an episode shares the same terminal reward as its return,
+// since this task has no intermediate rewards - only the final submit cost
+pub fn train_policy(
+    forecaster: &GasPriceNet,
+    device: Device,
+    config: AgentConfig,
+    n_episodes: usize,
+) -> nn::VarStore {
+    let vs = nn::VarStore::new(device);
+    // State is the forecaster's 7 input features plus its single forecast value
+    let policy = TimingPolicy::new(&vs.root(), 8, &config);
+    let mut opt = nn::Adam::default().build(&vs, config.learning_rate).unwrap();
+
+    for episode in 1..=n_episodes {
+        let (outcomes, reward) = run_episode(&policy, forecaster, device, &config);
+        let return_tensor = Tensor::from(reward as f32).to_device(device);
+
+        let mut policy_loss = Tensor::zeros(&[], (Kind::Float, device));
+        let mut value_loss = Tensor::zeros(&[], (Kind::Float, device));
+        let mut entropy_bonus = Tensor::zeros(&[], (Kind::Float, device));
+
+        for outcome in &outcomes {
+            // Advantage: how much better the actual return was than the
+            // critic's estimate, used to scale the policy gradient
+            let advantage = (&return_tensor - &outcome.value).detach();
+            policy_loss = policy_loss - &outcome.log_prob * advantage;
+            value_loss = value_loss + (&return_tensor - &outcome.value).pow_tensor_scalar(2);
+            entropy_bonus = entropy_bonus + &outcome.entropy;
+        }
+
+        let loss = policy_loss + value_loss - entropy_bonus * config.entropy_weight;
+        opt.backward_step(&loss);
+
+        if episode % 100 == 0 {
+            println!(
+                "Episode {:4}/{}: reward {:.2}",
+                episode, n_episodes, reward
+            );
+        }
+    }
+
+    vs
+}
+
+// Build a frozen `GasPriceNet` feature/forecast extractor by loading
+// previously trained weights; the agent never updates this model
+pub fn load_frozen_forecaster(model_path: &str, device: Device) -> (nn::VarStore, GasPriceNet) {
+    let mut vs = nn::VarStore::new(device);
+    let model = GasPriceNet::new(&vs.root(), &ModelConfig::default());
+    vs.load(model_path).unwrap();
+    (vs, model)
+}
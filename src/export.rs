@@ -0,0 +1,501 @@
+// Import PyTorch components for reading trained weights out of the var store
+use tch::nn;
+
+// ONNX stores tensor element types as small integers; 1 is FLOAT
+// See the `TensorProto.DataType` enum in onnx.proto
+const ONNX_ELEM_TYPE_FLOAT: i64 = 1;
+// ONNX attribute type for a plain int64 attribute (e.g. Gemm's `transB`)
+const ONNX_ATTR_TYPE_INT: i64 = 2;
+
+// Minimal protobuf wire-format helpers
+// The ONNX `ModelProto` schema only needs a handful of field kinds (varint,
+// length-delimited bytes/strings, and nested messages), so we write them
+// directly rather than pulling in a full protobuf/ONNX crate dependency
+mod wire {
+    pub fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value != 0 {
+                buf.push(byte | 0x80);
+            } else {
+                buf.push(byte);
+                break;
+            }
+        }
+    }
+
+    fn write_tag(buf: &mut Vec<u8>, field_number: u32, wire_type: u8) {
+        write_varint(buf, ((field_number as u64) << 3) | wire_type as u64);
+    }
+
+    pub fn write_varint_field(buf: &mut Vec<u8>, field_number: u32, value: i64) {
+        write_tag(buf, field_number, 0);
+        write_varint(buf, value as u64);
+    }
+
+    pub fn write_fixed32_field(buf: &mut Vec<u8>, field_number: u32, value: f32) {
+        write_tag(buf, field_number, 5);
+        buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_bytes_field(buf: &mut Vec<u8>, field_number: u32, bytes: &[u8]) {
+        write_tag(buf, field_number, 2);
+        write_varint(buf, bytes.len() as u64);
+        buf.extend_from_slice(bytes);
+    }
+
+    pub fn write_string_field(buf: &mut Vec<u8>, field_number: u32, value: &str) {
+        write_bytes_field(buf, field_number, value.as_bytes());
+    }
+
+    // A nested message is just a length-delimited bytes field
+    pub fn write_message_field(buf: &mut Vec<u8>, field_number: u32, message: &[u8]) {
+        write_bytes_field(buf, field_number, message);
+    }
+}
+
+use wire::{write_bytes_field, write_fixed32_field, write_message_field, write_string_field, write_varint_field};
+
+// Build a `TensorProto` holding one of our weight/bias tensors as an initializer
+fn encode_tensor(name: &str, dims: &[i64], data: &[f32]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for &dim in dims {
+        write_varint_field(&mut buf, 1, dim); // dims (repeated int64)
+    }
+    write_varint_field(&mut buf, 2, ONNX_ELEM_TYPE_FLOAT); // data_type
+    for &value in data {
+        write_fixed32_field(&mut buf, 4, value); // float_data (repeated float)
+    }
+    write_string_field(&mut buf, 8, name); // name
+    buf
+}
+
+// Build a `TensorShapeProto.Dimension` with a fixed value
+fn encode_dimension(value: i64) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_varint_field(&mut buf, 1, value); // dim_value
+    buf
+}
+
+// Build a `ValueInfoProto` describing a graph input/output's name and shape
+fn encode_value_info(name: &str, dims: &[i64]) -> Vec<u8> {
+    let mut shape_buf = Vec::new();
+    for &dim in dims {
+        let dim_buf = encode_dimension(dim);
+        write_message_field(&mut shape_buf, 1, &dim_buf); // dim (repeated)
+    }
+
+    let mut tensor_type_buf = Vec::new();
+    write_varint_field(&mut tensor_type_buf, 1, ONNX_ELEM_TYPE_FLOAT); // elem_type
+    write_message_field(&mut tensor_type_buf, 2, &shape_buf); // shape
+
+    let mut type_buf = Vec::new();
+    write_message_field(&mut type_buf, 1, &tensor_type_buf); // tensor_type (oneof)
+
+    let mut buf = Vec::new();
+    write_string_field(&mut buf, 1, name); // name
+    write_message_field(&mut buf, 2, &type_buf); // type
+    buf
+}
+
+// Build a `NodeProto` for one op in the graph (Gemm or Relu)
+fn encode_node(inputs: &[&str], outputs: &[&str], op_type: &str, name: &str, attributes: &[Vec<u8>]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for input in inputs {
+        write_string_field(&mut buf, 1, input); // input (repeated)
+    }
+    for output in outputs {
+        write_string_field(&mut buf, 2, output); // output (repeated)
+    }
+    write_string_field(&mut buf, 3, name); // name
+    write_string_field(&mut buf, 4, op_type); // op_type
+    for attribute in attributes {
+        write_message_field(&mut buf, 5, attribute); // attribute (repeated)
+    }
+    buf
+}
+
+// Build an `AttributeProto` holding a single int64 value, e.g. Gemm's `transB`
+fn encode_attribute_int(name: &str, value: i64) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_string_field(&mut buf, 1, name); // name
+    write_varint_field(&mut buf, 3, value); // i
+    write_varint_field(&mut buf, 20, ONNX_ATTR_TYPE_INT); // type
+    buf
+}
+
+// Read one linear layer's weight/bias tensors out of the var store by name,
+// as flat `f32` vectors, along with their shapes
+fn read_linear_params(vs: &nn::VarStore, prefix: &str) -> Option<(Vec<f32>, (i64, i64), Vec<f32>)> {
+    let variables = vs.variables();
+    let weight = variables.get(&format!("{}.weight", prefix))?;
+    let bias = variables.get(&format!("{}.bias", prefix))?;
+
+    let weight_size = weight.size();
+    let (out_features, in_features) = (weight_size[0], weight_size[1]);
+
+    let weight_data: Vec<f32> = Vec::<f32>::try_from(&weight.flatten(0, -1)).unwrap();
+    let bias_data: Vec<f32> = Vec::<f32>::try_from(bias).unwrap();
+
+    Some((weight_data, (out_features, in_features), bias_data))
+}
+
+// Export a trained `GasPriceNet` to an ONNX file
+// The network is a chain of Linear -> Relu pairs followed by a final Linear
+// (see `model::GasPriceNet`); this emits that exact graph, with weights and
+// biases read directly out of the var store as ONNX initializers, so the
+// model can run outside libtorch (web/wasm, onnxruntime, other bindings)
+pub fn export_to_onnx(vs: &nn::VarStore, output_path: &str) -> std::io::Result<()> {
+    let mut initializers = Vec::new();
+    let mut nodes = Vec::new();
+
+    let mut current_input = "input".to_string();
+    let mut layer_index = 0;
+    // The network's true input width, captured from the first hidden
+    // layer's `in_features` so the graph's input `ValueInfo` doesn't end up
+    // declaring the *last* layer's width instead (they differ whenever
+    // there's more than one hidden layer)
+    let mut network_input_size = None;
+
+    // Walk the hidden layers in order for as long as the var store has them
+    while let Some((weight, (out_features, in_features), bias)) =
+        read_linear_params(vs, &format!("hidden{}", layer_index))
+    {
+        if network_input_size.is_none() {
+            network_input_size = Some(in_features);
+        }
+
+        let weight_name = format!("hidden{}.weight", layer_index);
+        let bias_name = format!("hidden{}.bias", layer_index);
+        let gemm_output = format!("hidden{}_gemm", layer_index);
+        let relu_output = format!("hidden{}_relu", layer_index);
+
+        initializers.push(encode_tensor(&weight_name, &[out_features, in_features], &weight));
+        initializers.push(encode_tensor(&bias_name, &[out_features], &bias));
+
+        // transB=1 so Gemm computes X * W^T + b, matching `nn::Linear`'s layout
+        let trans_b = encode_attribute_int("transB", 1);
+        nodes.push(encode_node(
+            &[&current_input, &weight_name, &bias_name],
+            &[&gemm_output],
+            "Gemm",
+            &format!("hidden{}_gemm_node", layer_index),
+            &[trans_b],
+        ));
+        nodes.push(encode_node(
+            &[&gemm_output],
+            &[&relu_output],
+            "Relu",
+            &format!("hidden{}_relu_node", layer_index),
+            &[],
+        ));
+
+        current_input = relu_output;
+        layer_index += 1;
+    }
+
+    // Final output layer has no activation after it
+    let (output_weight, (out_features, in_features), output_bias) =
+        read_linear_params(vs, "output").expect("GasPriceNet must have an output layer");
+    initializers.push(encode_tensor("output.weight", &[out_features, in_features], &output_weight));
+    initializers.push(encode_tensor("output.bias", &[out_features], &output_bias));
+
+    let trans_b = encode_attribute_int("transB", 1);
+    nodes.push(encode_node(
+        &[&current_input, "output.weight", "output.bias"],
+        &["output"],
+        "Gemm",
+        "output_gemm_node",
+        &[trans_b],
+    ));
+
+    // Fall back to the output layer's `in_features` when there are no
+    // hidden layers at all, since it's then the network's only layer
+    let network_input_size = network_input_size.unwrap_or(in_features);
+
+    // -1 stands in for the dynamic batch dimension in both input and output shapes
+    let input_info = encode_value_info("input", &[-1, network_input_size]);
+    let output_info = encode_value_info("output", &[-1, out_features]);
+
+    let mut graph_buf = Vec::new();
+    for node in &nodes {
+        write_message_field(&mut graph_buf, 1, node); // node (repeated)
+    }
+    write_string_field(&mut graph_buf, 2, "GasPriceNet"); // name
+    for initializer in &initializers {
+        write_message_field(&mut graph_buf, 5, initializer); // initializer (repeated)
+    }
+    write_message_field(&mut graph_buf, 11, &input_info); // input (repeated)
+    write_message_field(&mut graph_buf, 12, &output_info); // output (repeated)
+
+    // OperatorSetIdProto: domain="" (the default ONNX domain), version=13
+    let mut opset_buf = Vec::new();
+    write_string_field(&mut opset_buf, 1, "");
+    write_varint_field(&mut opset_buf, 2, 13);
+
+    let mut model_buf = Vec::new();
+    write_varint_field(&mut model_buf, 1, 7); // ir_version
+    write_string_field(&mut model_buf, 2, "rust-eth-gas-ml"); // producer_name
+    write_message_field(&mut model_buf, 7, &graph_buf); // graph
+    write_message_field(&mut model_buf, 8, &opset_buf); // opset_import
+
+    std::fs::write(output_path, &model_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{GasPriceNet, ModelConfig};
+    use tch::{nn, nn::Module, Device, Tensor};
+
+    // Minimal reader for the subset of the ONNX wire format `export_to_onnx`
+    // writes: just enough to decode a `GraphProto`'s nodes/initializers and
+    // replay the Gemm->Relu chain ourselves, without pulling in an onnxruntime
+    // dependency just for this one test
+    mod read {
+        fn read_varint(buf: &[u8], pos: &mut usize) -> u64 {
+            let mut result = 0u64;
+            let mut shift = 0;
+            loop {
+                let byte = buf[*pos];
+                *pos += 1;
+                result |= ((byte & 0x7f) as u64) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                shift += 7;
+            }
+            result
+        }
+
+        // One top-level (field_number, payload) pair from a protobuf message;
+        // payload is the raw varint for wire type 0, or the raw bytes for the
+        // length-delimited (2) and fixed32 (5) wire types this format uses
+        struct Field {
+            number: u32,
+            varint: u64,
+            bytes: Vec<u8>,
+        }
+
+        fn read_fields(buf: &[u8]) -> Vec<Field> {
+            let mut pos = 0;
+            let mut fields = Vec::new();
+            while pos < buf.len() {
+                let tag = read_varint(buf, &mut pos);
+                let number = (tag >> 3) as u32;
+                let wire_type = (tag & 0x7) as u8;
+                match wire_type {
+                    0 => fields.push(Field { number, varint: read_varint(buf, &mut pos), bytes: Vec::new() }),
+                    5 => {
+                        let bytes = buf[pos..pos + 4].to_vec();
+                        pos += 4;
+                        fields.push(Field { number, varint: 0, bytes });
+                    }
+                    2 => {
+                        let len = read_varint(buf, &mut pos) as usize;
+                        let bytes = buf[pos..pos + len].to_vec();
+                        pos += len;
+                        fields.push(Field { number, varint: 0, bytes });
+                    }
+                    other => panic!("unexpected wire type {} in test ONNX decoder", other),
+                }
+            }
+            fields
+        }
+
+        // Decoded `TensorProto`: just the pieces `encode_tensor` writes
+        pub struct DecodedTensor {
+            pub dims: Vec<i64>,
+            pub data: Vec<f32>,
+        }
+
+        fn decode_tensor(buf: &[u8]) -> (String, DecodedTensor) {
+            let mut dims = Vec::new();
+            let mut data = Vec::new();
+            let mut name = String::new();
+            for field in read_fields(buf) {
+                match field.number {
+                    1 => dims.push(field.varint as i64),
+                    4 => data.push(f32::from_le_bytes(field.bytes.try_into().unwrap())),
+                    8 => name = String::from_utf8(field.bytes).unwrap(),
+                    _ => {}
+                }
+            }
+            (name, DecodedTensor { dims, data })
+        }
+
+        // Decoded `NodeProto`: just the pieces `encode_node` writes
+        pub struct DecodedNode {
+            pub inputs: Vec<String>,
+            pub outputs: Vec<String>,
+            pub op_type: String,
+        }
+
+        fn decode_node(buf: &[u8]) -> DecodedNode {
+            let mut inputs = Vec::new();
+            let mut outputs = Vec::new();
+            let mut op_type = String::new();
+            for field in read_fields(buf) {
+                match field.number {
+                    1 => inputs.push(String::from_utf8(field.bytes).unwrap()),
+                    2 => outputs.push(String::from_utf8(field.bytes).unwrap()),
+                    4 => op_type = String::from_utf8(field.bytes).unwrap(),
+                    _ => {}
+                }
+            }
+            DecodedNode { inputs, outputs, op_type }
+        }
+
+        // Pull the declared dims out of a `ValueInfoProto`, following the
+        // same nested `type -> tensor_type -> shape -> dim -> dim_value`
+        // path `encode_value_info` writes
+        fn decode_value_info(buf: &[u8]) -> (String, Vec<i64>) {
+            let mut name = String::new();
+            let mut dims = Vec::new();
+            for field in read_fields(buf) {
+                match field.number {
+                    1 => name = String::from_utf8(field.bytes).unwrap(),
+                    2 => {
+                        for type_field in read_fields(&field.bytes).iter().filter(|f| f.number == 1) {
+                            for tensor_type_field in
+                                read_fields(&type_field.bytes).iter().filter(|f| f.number == 2)
+                            {
+                                for shape_field in
+                                    read_fields(&tensor_type_field.bytes).iter().filter(|f| f.number == 1)
+                                {
+                                    for dim_field in
+                                        read_fields(&shape_field.bytes).iter().filter(|f| f.number == 1)
+                                    {
+                                        dims.push(dim_field.varint as i64);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            (name, dims)
+        }
+
+        pub struct DecodedGraph {
+            pub nodes: Vec<DecodedNode>,
+            pub initializers: std::collections::HashMap<String, DecodedTensor>,
+            // (name, dims) for the graph's declared input/output ValueInfo
+            pub input: (String, Vec<i64>),
+            pub output: (String, Vec<i64>),
+        }
+
+        fn decode_graph(buf: &[u8]) -> DecodedGraph {
+            let mut nodes = Vec::new();
+            let mut initializers = std::collections::HashMap::new();
+            let mut input = (String::new(), Vec::new());
+            let mut output = (String::new(), Vec::new());
+            for field in read_fields(buf) {
+                match field.number {
+                    1 => nodes.push(decode_node(&field.bytes)),
+                    5 => {
+                        let (name, tensor) = decode_tensor(&field.bytes);
+                        initializers.insert(name, tensor);
+                    }
+                    11 => input = decode_value_info(&field.bytes),
+                    12 => output = decode_value_info(&field.bytes),
+                    _ => {}
+                }
+            }
+            DecodedGraph { nodes, initializers, input, output }
+        }
+
+        // Decode the top-level `ModelProto` and pull out its graph
+        pub fn decode_model(buf: &[u8]) -> DecodedGraph {
+            for field in read_fields(buf) {
+                if field.number == 7 {
+                    return decode_graph(&field.bytes);
+                }
+            }
+            panic!("ModelProto has no graph field");
+        }
+
+        // Replay the Gemm->Relu chain over a single input row, matching
+        // ONNX's Gemm semantics with transB=1: y = x @ W^T + b
+        impl DecodedGraph {
+            pub fn run(&self, input: &[f32]) -> Vec<f32> {
+                let mut values: std::collections::HashMap<String, Vec<f32>> = std::collections::HashMap::new();
+                values.insert("input".to_string(), input.to_vec());
+
+                for node in &self.nodes {
+                    let x = values.get(&node.inputs[0]).unwrap().clone();
+                    let output_name = node.outputs[0].clone();
+                    let result = match node.op_type.as_str() {
+                        "Relu" => x.iter().map(|v| v.max(0.0)).collect(),
+                        "Gemm" => {
+                            let weight = &self.initializers[&node.inputs[1]];
+                            let bias = &self.initializers[&node.inputs[2]];
+                            let (out_features, in_features) = (weight.dims[0] as usize, weight.dims[1] as usize);
+                            (0..out_features)
+                                .map(|o| {
+                                    let dot: f32 = (0..in_features)
+                                        .map(|i| x[i] * weight.data[o * in_features + i])
+                                        .sum();
+                                    dot + bias.data[o]
+                                })
+                                .collect()
+                        }
+                        other => panic!("test ONNX interpreter doesn't support op {}", other),
+                    };
+                    values.insert(output_name, result);
+                }
+
+                values["output"].clone()
+            }
+        }
+    }
+
+    #[test]
+    fn exported_graph_matches_tch_forward_within_tolerance() {
+        let device = Device::Cpu;
+        let vs = nn::VarStore::new(device);
+        let config = ModelConfig::default();
+        let model = GasPriceNet::new(&vs.root(), &config);
+
+        let output_path = std::env::temp_dir().join("gas_model_export_roundtrip_test.onnx");
+        export_to_onnx(&vs, output_path.to_str().unwrap()).unwrap();
+
+        let onnx_bytes = std::fs::read(&output_path).unwrap();
+        std::fs::remove_file(&output_path).ok();
+        let graph = read::decode_model(&onnx_bytes);
+
+        // The declared input/output shapes are what makes the file usable
+        // by onnxruntime/wasm in the first place; a graph that merely
+        // *computes* the right numbers but declares the wrong input width
+        // would still fail to load there
+        assert_eq!(
+            graph.input.1,
+            vec![-1, config.input_size],
+            "declared input shape should be [batch, {}], got {:?}",
+            config.input_size,
+            graph.input.1
+        );
+        assert_eq!(
+            graph.output.1,
+            vec![-1, config.output_size],
+            "declared output shape should be [batch, {}], got {:?}",
+            config.output_size,
+            graph.output.1
+        );
+
+        let example: [f32; 7] = [150.0, 500.0, 0.85, 0.90, 14.0, 200.0, 0.0];
+        let onnx_output = graph.run(&example)[0];
+
+        let input = Tensor::of_slice(&example).unsqueeze(0);
+        let tch_output = f64::from(tch::no_grad(|| model.forward(&input))) as f32;
+
+        assert!(
+            (onnx_output - tch_output).abs() < 1e-3,
+            "ONNX graph output {} diverged from tch forward pass {}",
+            onnx_output,
+            tch_output
+        );
+    }
+}
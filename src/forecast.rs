@@ -0,0 +1,81 @@
+// Import the recurrent model this module forecasts with
+use crate::model::GasPriceSeqNet;
+// Import tensor and module types from tch
+use tch::{nn::Module, Tensor};
+
+// Number of simulated seconds between successive blocks
+// Matches the block cadence used to build the training windows in `data.rs`
+const SECONDS_PER_BLOCK: f64 = 12.0;
+
+// Function to forecast a full horizon by rolling predictions forward
+// Feeds each prediction back into the window to produce the next one
+//
+// NOTE on error accumulation: because step t+1's prediction becomes part of
+// the input for step t+2, any error in an early prediction propagates and
+// compounds into every later step. Near-term forecasts are reasonably
+// accurate, but predictions far into the horizon drift away from the true
+// trajectory since there is no real observation to correct the model along
+// the way - this is an inherent property of recursive (autoregressive)
+// forecasting, not a bug in the model or the loop below.
+pub fn forecast_horizon(model: &GasPriceSeqNet, seed_window: &Tensor, steps: usize) -> Vec<f64> {
+    // Start from the caller's seed window, shape [1, seq_len, 7]
+    // We'll slide this window forward by one block each iteration
+    let mut window = seed_window.shallow_clone();
+
+    // Pre-allocate the output trajectory
+    // One predicted price per step in the horizon
+    let mut predictions = Vec::with_capacity(steps);
+
+    // Number of blocks in the window, used to build the next row
+    let seq_len = window.size()[1];
+
+    // Capture the seed window's own time position so `hour` and `weekend`
+    // can be advanced from actual elapsed simulated time rather than having
+    // `hour` wrap on itself with no day counter behind it. Only the binary
+    // weekend flag (not a day-of-week index) is available from the seed, so
+    // assume it sits on the first day of whichever run (weekday/weekend) it
+    // reports - same assumption `data::generate_gas_block_series` makes by
+    // starting its own day-of-week count at zero.
+    let seed_last_row = window.select(0, 0).select(0, seq_len - 1);
+    let seed_hour = f64::from(seed_last_row.get(4));
+    let seed_weekend = f64::from(seed_last_row.get(6));
+    let seed_day_of_week: u64 = if seed_weekend == 1.0 { 5 } else { 0 };
+
+    // Running count of simulated seconds elapsed since the seed window's
+    // last block; `hour` and `weekend` are recomputed from this each step
+    let mut seconds_elapsed = 0.0;
+
+    // Roll the prediction forward one block at a time
+    for _ in 0..steps {
+        // Run inference under no_grad since we're not training here
+        let prediction = tch::no_grad(|| model.forward(&window));
+        let predicted_price = f64::from(&prediction);
+        predictions.push(predicted_price);
+
+        // Build the next block's feature row from the window's last row,
+        // carrying the prediction in as the new base fee and advancing the
+        // deterministic time features; the remaining features (pending tx,
+        // utilization, high priority count) are carried over unchanged since
+        // we have no model for how they evolve on their own
+        let last_row = window.select(0, 0).select(0, seq_len - 1);
+        let mut next_row: Vec<f64> = (0..7).map(|i| f64::from(last_row.get(i))).collect();
+
+        // base_fee <- this step's forecast, feeding the prediction back in
+        next_row[0] = predicted_price;
+
+        // Advance both time features together from elapsed simulated time,
+        // rather than advancing `hour` alone and never updating `weekend`
+        seconds_elapsed += SECONDS_PER_BLOCK;
+        let total_hours = seed_hour + seconds_elapsed / 3600.0;
+        next_row[4] = total_hours % 24.0;
+        let days_passed = (total_hours / 24.0).floor() as u64;
+        let day_of_week = (seed_day_of_week + days_passed) % 7;
+        next_row[6] = if day_of_week >= 5 { 1.0 } else { 0.0 };
+
+        // Append the new row and drop the oldest one to keep a fixed-length window
+        let next_tensor = Tensor::of_slice(&next_row).reshape(&[1, 1, 7]);
+        window = Tensor::cat(&[window.narrow(1, 1, seq_len - 1), next_tensor], 1);
+    }
+
+    predictions
+}
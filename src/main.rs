@@ -1,8 +1,13 @@
 // Import required modules from our project
 // These modules contain model definition, data generation, and training logic
+mod agent;
 mod data;
+mod export;
+mod forecast;
 mod model;
+mod serve;
 mod train;
+mod tune;
 
 // Import necessary items from tch crate
 // Device represents CPU/GPU, Tensor is the main data structure
@@ -13,19 +18,76 @@ use ansi_term::Colour::{Blue, Green, Red, Yellow};
 // Main entry point of our program
 // This function orchestrates training and inference demo
 fn main() {
-    // Print welcome message with colored output
-    // Makes it clear the program has started
-    println!("{}", Blue.bold().paint("\nEthereum Gas Price Predictor"));
-    println!("{}", Blue.paint("================================\n"));
-
     // Set the computation device (CPU in this case)
     // Could be Device::Cuda(0) for GPU if available
     let device = Device::Cpu;
-    
+
     // Define model save path
     // This is where we'll save/load our trained model
     let model_path = "gas_model.pt";
 
+    // `--serve` runs the JSON HTTP inference server instead of the training/demo flow
+    // Expects a model already trained and saved at `model_path`
+    if std::env::args().any(|arg| arg == "--serve") {
+        println!("{}", Blue.bold().paint("\nEthereum Gas Price Predictor - Serving"));
+        println!("{}", Blue.paint("=======================================\n"));
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(serve::serve(model_path, device, "0.0.0.0:3000"));
+        return;
+    }
+
+    // `--tune` runs the hyperparameter search over architecture and
+    // optimizer settings instead of the training/demo flow, printing the
+    // best trial found
+    if std::env::args().any(|arg| arg == "--tune") {
+        println!("{}", Blue.bold().paint("\nGas Price Model Hyperparameter Search"));
+        println!("{}", Blue.paint("=======================================\n"));
+        let (train_features, train_labels) = data::generate_gas_data(2000);
+        let (val_features, val_labels) = data::generate_gas_data(500);
+        let space = tune::SearchSpace {
+            hidden_size_choices: vec![32, 64, 128],
+            n_hidden_layers_choices: vec![1, 2],
+            learning_rate_choices: vec![1e-2, 1e-3, 1e-4],
+            batch_size_choices: vec![16, 32, 64],
+        };
+        let best = tune::random_search(
+            &train_features,
+            &train_labels,
+            &val_features,
+            &val_labels,
+            device,
+            &space,
+            10,
+            20,
+        );
+        println!(
+            "{}",
+            Green.bold().paint(format!(
+                "Best trial: hidden_sizes {:?}, lr {:.4}, batch_size {}, val RMSE {:.4}",
+                best.model_config.hidden_sizes, best.learning_rate, best.batch_size, best.val_rmse
+            ))
+        );
+        return;
+    }
+
+    // `--train-agent` trains a submit-or-wait timing policy on top of an
+    // already-trained, frozen forecaster instead of the training/demo flow
+    if std::env::args().any(|arg| arg == "--train-agent") {
+        println!("{}", Blue.bold().paint("\nGas-Timing Agent Training"));
+        println!("{}", Blue.paint("==========================\n"));
+        let (_forecaster_vs, forecaster) = agent::load_frozen_forecaster(model_path, device);
+        let agent_vs = agent::train_policy(&forecaster, device, agent::AgentConfig::default(), 1000);
+        let agent_path = "gas_timing_agent.pt";
+        agent_vs.save(agent_path).unwrap();
+        println!("{}", Green.bold().paint(format!("Agent policy saved to {}", agent_path)));
+        return;
+    }
+
+    // Print welcome message with colored output
+    // Makes it clear the program has started
+    println!("{}", Blue.bold().paint("\nEthereum Gas Price Predictor"));
+    println!("{}", Blue.paint("================================\n"));
+
     // Generate synthetic training data
     // Returns features and labels for training
     println!("{}", Yellow.paint("Generating synthetic gas price data..."));
@@ -58,6 +120,14 @@ fn main() {
         model_path,
     );
 
+    // `--export-onnx` exports the just-trained model so it can run outside
+    // libtorch (web/wasm, onnxruntime, other language bindings)
+    if std::env::args().any(|arg| arg == "--export-onnx") {
+        let onnx_path = "gas_model.onnx";
+        export::export_to_onnx(&vs, onnx_path).unwrap();
+        println!("{}", Green.paint(format!("Exported ONNX model to {}", onnx_path)));
+    }
+
     // Inference demo section
     // Shows how to use the trained model for predictions
     println!("\n{}", Blue.bold().paint("Inference Demo"));
@@ -77,7 +147,7 @@ fn main() {
 
     // Load the trained model for inference
     // Create a new model instance with the saved weights
-    let model = model::GasPriceNet::new(&vs.root());
+    let model = model::GasPriceNet::new(&vs.root(), &model::ModelConfig::default());
     
     // Process each example and make predictions
     // Demonstrates real-world usage of the model
@@ -113,6 +183,118 @@ fn main() {
         )));
     }
 
+    // Multi-step forecast demo section
+    // Shows the recurrent model rolling a prediction forward over a horizon
+    println!("\n{}", Blue.bold().paint("Multi-Step Forecast Demo"));
+    println!("{}", Blue.paint("=========================\n"));
+
+    // Train the recurrent model on an ordered block series before demoing
+    // its forecast - a demo built on a fresh, untrained VarStore would just
+    // roll random noise forward
+    println!("{}", Yellow.paint("Training recurrent forecaster on block series..."));
+    let seq_len = model::SEQ_LEN as usize;
+    let (seq_train_features, seq_train_labels) = data::generate_gas_sequence_data(2000, seq_len);
+    let seq_vs = train::train_seq_model(&seq_train_features, &seq_train_labels, device, 50, 32, 1e-3);
+    // Build the model on its own VarStore and copy the trained weights in;
+    // building it again on `seq_vs` would register fresh, untrained variables
+    let mut seq_model_vs = tch::nn::VarStore::new(device);
+    let seq_model = model::GasPriceSeqNet::new(&seq_model_vs.root());
+    seq_model_vs.copy(&seq_vs).unwrap();
+
+    // Generate one ordered block series and take its first window as the seed
+    // `generate_gas_sequence_data` returns sliding windows; we only need one
+    let (seed_windows, _) = data::generate_gas_sequence_data(seq_len + 1, seq_len);
+    let seed_window = seed_windows.narrow(0, 0, 1);
+
+    // Roll the forecast forward over the next 12 blocks (~2.5 minutes)
+    let horizon_steps = 12;
+    let trajectory = forecast::forecast_horizon(&seq_model, &seed_window, horizon_steps);
+
+    println!("{}", Yellow.paint(format!(
+        "Forecast trajectory for the next {} blocks:",
+        horizon_steps
+    )));
+    for (step, price) in trajectory.iter().enumerate() {
+        println!("  Block t+{}: {:.2} gwei", step + 1, price);
+    }
+    println!("{}", Red.paint(
+        "Note: predictions this far ahead are recursive and accumulate error - treat later blocks as directional, not exact.\n"
+    ));
+
+    // EIP-1559 baseline comparison section
+    // Shows how much the trained network improves over the protocol's own
+    // deterministic base-fee update rule
+    println!("\n{}", Blue.bold().paint("EIP-1559 Baseline Comparison"));
+    println!("{}", Blue.paint("=============================\n"));
+
+    // Simulate a short run of protocol-accurate blocks to compare against;
+    // one extra block so every printed block has a real "next" value to
+    // score both the baseline and the net's predictions against
+    let eip1559_blocks = data::simulate_eip1559_blocks(6);
+
+    // Train a GasPriceNet on the protocol-accurate series itself, not the
+    // i.i.d. synthetic data trained above, so the net actually learns
+    // EIP-1559 dynamics before it's compared against the protocol rule
+    println!("{}", Yellow.paint("Training neural net on EIP-1559 block series..."));
+    let (eip1559_train_features, eip1559_train_labels) = data::generate_eip1559_gas_data(3000);
+    let (eip1559_val_features, eip1559_val_labels) = data::generate_eip1559_gas_data(500);
+    let eip1559_learner_config = train::LearnerConfig {
+        n_epochs: 30,
+        validate_every: 5,
+        ..Default::default()
+    };
+    let mut eip1559_learner = train::Learner::new(
+        device,
+        "gas_model_eip1559.pt",
+        model::ModelConfig::default(),
+        eip1559_learner_config,
+    );
+    eip1559_learner.fit(
+        &eip1559_train_features,
+        &eip1559_train_labels,
+        &eip1559_val_features,
+        &eip1559_val_labels,
+    );
+    let eip1559_vs = eip1559_learner.into_var_store();
+    let mut eip1559_model_vs = tch::nn::VarStore::new(device);
+    let eip1559_model = model::GasPriceNet::new(&eip1559_model_vs.root(), &model::ModelConfig::default());
+    eip1559_model_vs.copy(&eip1559_vs).unwrap();
+
+    for i in 0..eip1559_blocks.len() - 1 {
+        let block = &eip1559_blocks[i];
+        let next_block = &eip1559_blocks[i + 1];
+
+        // The non-ML baseline: forecast next base fee directly from the protocol rule
+        let baseline_prediction =
+            data::eip1559_baseline_predict(block.base_fee, block.gas_used, block.gas_target);
+        let baseline_error = (baseline_prediction - next_block.base_fee).abs();
+
+        // The trained net's forecast of next gas price (base fee + priority tip)
+        let features = data::eip1559_block_features(&eip1559_blocks, i);
+        let input = Tensor::of_slice(&features).to_device(device).unsqueeze(0);
+        let net_prediction = f64::from(tch::no_grad(|| eip1559_model.forward(&input)));
+        let actual_next_gas_price = next_block.base_fee + next_block.priority_fee;
+        let net_error = (net_prediction - actual_next_gas_price).abs();
+
+        println!(
+            "{}",
+            Yellow.paint(format!(
+                "Block {}: base_fee {:.2} gwei, utilization {:.1}%",
+                i,
+                block.base_fee,
+                block.gas_used / (block.gas_target * 2.0) * 100.0
+            ))
+        );
+        println!(
+            "  -> EIP-1559 baseline next base fee: {:.2} gwei (actual {:.2}, error {:.2})",
+            baseline_prediction, next_block.base_fee, baseline_error
+        );
+        println!(
+            "  -> Neural net next gas price: {:.2} gwei (actual {:.2}, error {:.2})",
+            net_prediction, actual_next_gas_price, net_error
+        );
+    }
+
     // Print completion message
     // Indicates successful execution
     println!("{}", Blue.bold().paint("Demo complete!"));
@@ -1,6 +1,6 @@
 // Import neural network module from tch
 // nn provides layers and model building blocks
-use tch::{nn, nn::Module, Tensor};
+use tch::{nn, nn::Module, nn::RNN, Tensor};
 
 // Define constants for network architecture
 // These control the size and capacity of our model
@@ -13,32 +13,74 @@ const HIDDEN_SIZE: i64 = 64;
 // Single value: predicted gas price
 const OUTPUT_SIZE: i64 = 1;
 
+// Number of blocks fed into the recurrent model per training sample
+// 24 blocks gives roughly 5 minutes of mainnet history per window
+pub const SEQ_LEN: i64 = 24;
+
+// Config describing a `GasPriceNet`'s shape
+// Lets callers (e.g. the hyperparameter tuner) instantiate variable-width
+// and variable-depth networks instead of being stuck with the fixed 7->64->1 shape
+#[derive(Clone, Debug)]
+pub struct ModelConfig {
+    // Number of input features
+    pub input_size: i64,
+    // Width of each hidden layer, in order; one `nn::Linear` per entry
+    pub hidden_sizes: Vec<i64>,
+    // Number of outputs (1 for regression)
+    pub output_size: i64,
+}
+
+// Default config reproduces the network's original fixed 7->64->1 shape
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self {
+            input_size: INPUT_SIZE,
+            hidden_sizes: vec![HIDDEN_SIZE],
+            output_size: OUTPUT_SIZE,
+        }
+    }
+}
+
 // Define our neural network structure
-// Implements a 2-layer feedforward network
+// Implements a configurable-depth feedforward network
 pub struct GasPriceNet {
-    // First fully connected layer: input -> hidden
-    // Transforms 7 features to 64 hidden units
-    fc1: nn::Linear,
-    // Second fully connected layer: hidden -> output  
-    // Transforms 64 hidden units to 1 prediction
-    fc2: nn::Linear,
+    // Hidden layers, applied in order with a ReLU between each
+    hidden_layers: Vec<nn::Linear>,
+    // Final layer: last hidden size -> output
+    // Has no activation applied after it
+    output_layer: nn::Linear,
 }
 
 // Implementation block for model methods
 impl GasPriceNet {
     // Constructor to create new model instance
-    // Takes a path from variable store for parameter registration
-    pub fn new(vs: &nn::Path) -> Self {
-        // Initialize first layer with Xavier/He initialization
-        // / "fc1" creates a subpath for these parameters
-        let fc1 = nn::linear(vs / "fc1", INPUT_SIZE, HIDDEN_SIZE, Default::default());
+    // Takes a path from variable store and a shape config for parameter registration
+    pub fn new(vs: &nn::Path, config: &ModelConfig) -> Self {
+        // Build one linear layer per entry in `hidden_sizes`, chaining widths together
+        // / "hidden{i}" keeps each layer's parameters organized under its own subpath
+        let mut hidden_layers = Vec::with_capacity(config.hidden_sizes.len());
+        let mut prev_size = config.input_size;
+        for (i, &hidden_size) in config.hidden_sizes.iter().enumerate() {
+            let layer = nn::linear(
+                vs / format!("hidden{}", i),
+                prev_size,
+                hidden_size,
+                Default::default(),
+            );
+            hidden_layers.push(layer);
+            prev_size = hidden_size;
+        }
+
         // Initialize output layer
-        // / "fc2" keeps parameters organized
-        let fc2 = nn::linear(vs / "fc2", HIDDEN_SIZE, OUTPUT_SIZE, Default::default());
-        
+        // / "output" keeps parameters organized
+        let output_layer = nn::linear(vs / "output", prev_size, config.output_size, Default::default());
+
         // Return the constructed model
-        // Both layers are now registered in the variable store
-        Self { fc1, fc2 }
+        // All layers are now registered in the variable store
+        Self {
+            hidden_layers,
+            output_layer,
+        }
     }
 }
 
@@ -48,14 +90,62 @@ impl Module for GasPriceNet {
     // Forward propagation function
     // Takes input tensor and returns predictions
     fn forward(&self, xs: &Tensor) -> Tensor {
-        // Apply first linear transformation
-        // Converts input features to hidden representation
-        xs.apply(&self.fc1)
-            // Apply ReLU activation function
-            // Introduces non-linearity for learning complex patterns
-            .relu()
-            // Apply second linear transformation
-            // Produces final gas price prediction
-            .apply(&self.fc2)
+        // Apply each hidden layer followed by a ReLU activation
+        // Introduces non-linearity for learning complex patterns
+        let mut activations = xs.shallow_clone();
+        for layer in &self.hidden_layers {
+            activations = activations.apply(layer).relu();
+        }
+
+        // Apply the output layer
+        // Produces the final gas price prediction, no activation afterward
+        activations.apply(&self.output_layer)
+    }
+}
+
+// Define our recurrent network structure
+// Reads a window of `SEQ_LEN` past blocks and forecasts the next one
+pub struct GasPriceSeqNet {
+    // LSTM layer: consumes the [batch, seq_len, 7] window
+    // Carries a hidden state forward across the sequence
+    lstm: nn::LSTM,
+    // Output head: hidden state -> single price forecast
+    // Same role as the output layer in the feedforward model
+    fc_out: nn::Linear,
+}
+
+// Implementation block for recurrent model methods
+impl GasPriceSeqNet {
+    // Constructor to create new sequence model instance
+    // Takes a path from variable store for parameter registration
+    pub fn new(vs: &nn::Path) -> Self {
+        // Initialize LSTM with default config (single layer, no dropout)
+        // / "lstm" creates a subpath for these parameters
+        let lstm = nn::lstm(vs / "lstm", INPUT_SIZE, HIDDEN_SIZE, Default::default());
+        // Initialize output layer
+        // / "fc_out" keeps parameters organized
+        let fc_out = nn::linear(vs / "fc_out", HIDDEN_SIZE, OUTPUT_SIZE, Default::default());
+
+        // Return the constructed model
+        // Both the LSTM and output head are now registered in the variable store
+        Self { lstm, fc_out }
+    }
+}
+
+// Implement the Module trait for forward pass
+// Same calling convention as GasPriceNet: one window in, one forecast out
+impl Module for GasPriceSeqNet {
+    // Forward propagation function
+    // Takes a [batch, seq_len, 7] window and returns a [batch, 1] forecast
+    fn forward(&self, xs: &Tensor) -> Tensor {
+        // Run the LSTM across the whole window
+        // `seq` returns the per-step outputs and the final hidden/cell state
+        let (_, state) = self.lstm.seq(xs);
+        // `state.h()` is [num_layers, batch, hidden]; select the last (and,
+        // with this constructor, only) layer to get back to [batch, hidden]
+        // before the batch dimension is indistinguishable from num_layers
+        let last_layer_hidden = state.h().select(0, -1);
+        // Apply the output head to produce the forecast
+        last_layer_hidden.apply(&self.fc_out)
     }
 }
\ No newline at end of file
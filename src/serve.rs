@@ -0,0 +1,99 @@
+// Import model types this server loads and runs
+use crate::model::{GasPriceNet, ModelConfig};
+// Import tch components for loading the trained model and running inference
+use tch::{nn, nn::Module, Device, Tensor};
+// Import axum for the JSON HTTP endpoint
+use axum::{extract::State, routing::get, routing::post, Json, Router};
+// Import serde for request/response (de)serialization
+use serde::{Deserialize, Serialize};
+// Shared, reference-counted state so every request reuses the same loaded model
+use std::sync::Arc;
+
+// Request body: one or more 7-feature rows to score in a single call
+// Supporting a batch lets callers amortize a network round trip across
+// many predictions instead of making one request per row
+#[derive(Deserialize)]
+pub struct PredictRequest {
+    pub rows: Vec<[f64; 7]>,
+}
+
+// Response body: one predicted gas price per input row, in the same order
+#[derive(Serialize)]
+pub struct PredictResponse {
+    pub predictions: Vec<f64>,
+}
+
+// Response body for the health/list endpoint
+#[derive(Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+    pub model_path: String,
+}
+
+// Server state shared across requests: the model is loaded once at startup
+// and reused for every call, rather than reloading weights per request
+struct ServerState {
+    model: GasPriceNet,
+    device: Device,
+    model_path: String,
+}
+
+// Handler for POST /predict
+// Runs a batch of feature rows through the model under `no_grad`
+async fn predict(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<PredictRequest>,
+) -> Json<PredictResponse> {
+    // Flatten the request rows into a single [n, 7] batch tensor
+    let n_rows = request.rows.len();
+    let flat: Vec<f64> = request.rows.into_iter().flatten().collect();
+    let input = Tensor::of_slice(&flat)
+        .to_device(state.device)
+        .reshape(&[n_rows as i64, 7]);
+
+    // Disable gradient computation for inference
+    // Saves memory and computation, same as the CLI demo in `main`
+    let output = tch::no_grad(|| state.model.forward(&input));
+    let predictions: Vec<f64> = (0..n_rows as i64).map(|i| f64::from(output.get(i))).collect();
+
+    Json(PredictResponse { predictions })
+}
+
+// Handler for GET /health
+// Reports that the server is up and which model weights it's serving
+async fn health(State(state): State<Arc<ServerState>>) -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok",
+        model_path: state.model_path.clone(),
+    })
+}
+
+// Build the router with the model loaded once and shared across requests
+fn build_router(model_path: &str, device: Device) -> Router {
+    // Load the trained weights into a fresh variable store
+    // This manages all trainable weights and biases
+    let mut vs = nn::VarStore::new(device);
+    let model = GasPriceNet::new(&vs.root(), &ModelConfig::default());
+    vs.load(model_path).unwrap();
+
+    let state = Arc::new(ServerState {
+        model,
+        device,
+        model_path: model_path.to_string(),
+    });
+
+    Router::new()
+        .route("/health", get(health))
+        .route("/predict", post(predict))
+        .with_state(state)
+}
+
+// Start the JSON HTTP inference server on the given address
+// Serves requests until the process is stopped; other applications (wallets,
+// bots) can query this instead of embedding libtorch themselves
+pub async fn serve(model_path: &str, device: Device, addr: &str) {
+    let app = build_router(model_path, device);
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    println!("Gas price inference server listening on {}", addr);
+    axum::serve(listener, app).await.unwrap();
+}
@@ -1,12 +1,300 @@
-// Import model structure from our model module
-use crate::model::GasPriceNet;
+// Import model structures from our model module
+use crate::model::{GasPriceNet, GasPriceSeqNet, ModelConfig};
 // Import colored output for training progress
 use ansi_term::Colour::{Green, Red, Yellow};
 // Import PyTorch components for training
-use tch::{nn, nn::OptimizerConfig, Device, Tensor};
+use tch::{nn, nn::Module, nn::OptimizerConfig, Device, Kind, Tensor};
+
+// Regression metrics computed on a validation pass
+// Reported alongside the raw MSE loss so users can judge fit in interpretable units
+pub struct RegressionMetrics {
+    // Mean absolute error, in the same units as the gas price label (gwei)
+    pub mae: f64,
+    // Root mean squared error, in the same units as the gas price label (gwei)
+    pub rmse: f64,
+    // Coefficient of determination: 1 - SS_res/SS_tot, 1.0 is a perfect fit
+    pub r2: f64,
+}
+
+// One epoch's worth of training/validation stats
+// Only populated with val_loss/val_metrics on epochs where validation ran
+pub struct EpochRecord {
+    pub epoch: i64,
+    pub train_loss: f64,
+    pub val_loss: Option<f64>,
+    pub val_metrics: Option<RegressionMetrics>,
+}
+
+// Full record of a training run, returned to the caller for plotting/logging
+pub struct TrainingHistory {
+    // One entry per epoch, in order
+    pub epochs: Vec<EpochRecord>,
+    // Epoch number of the best checkpoint (lowest validation loss seen)
+    pub best_epoch: i64,
+    // Validation loss (MSE) at the best checkpoint
+    pub best_val_loss: f64,
+    // Validation RMSE at the best checkpoint, in gwei
+    pub best_val_rmse: f64,
+    // True if training stopped early due to lack of validation improvement
+    pub stopped_early: bool,
+}
+
+// Hyperparameters controlling a `Learner`'s training run
+// Mirrors the constants the old `train_model` hardcoded, now overridable
+pub struct LearnerConfig {
+    // Number of complete passes through the dataset
+    pub n_epochs: i64,
+    // Number of samples per gradient update
+    pub batch_size: i64,
+    // Adam learning rate
+    pub learning_rate: f64,
+    // Run a validation pass every this many epochs
+    pub validate_every: i64,
+    // Stop once validation loss fails to improve for this many consecutive
+    // validation passes (patience is measured in checks, not epochs)
+    pub patience: i64,
+}
+
+// Defaults reuse the original `train_model`'s epochs, batch size, learning
+// rate and validation cadence; patience-based early stopping is new
+impl Default for LearnerConfig {
+    fn default() -> Self {
+        Self {
+            n_epochs: 100,
+            batch_size: 32,
+            learning_rate: 1e-3,
+            validate_every: 10,
+            patience: 5,
+        }
+    }
+}
+
+// The Learner owns the model, optimizer, and training loop
+// Call `fit` to run training with early stopping and best-checkpoint saving
+pub struct Learner {
+    vs: nn::VarStore,
+    model: GasPriceNet,
+    opt: nn::Optimizer,
+    config: LearnerConfig,
+    model_path: String,
+}
+
+impl Learner {
+    // Construct a new learner: creates the variable store, model, and optimizer
+    pub fn new(device: Device, model_path: &str, model_config: ModelConfig, config: LearnerConfig) -> Self {
+        // Create variable store to hold model parameters
+        // This manages all trainable weights and biases
+        let vs = nn::VarStore::new(device);
+
+        // Initialize the model with the variable store
+        // Creates layers and registers parameters
+        let model = GasPriceNet::new(&vs.root(), &model_config);
+
+        // Create Adam optimizer with the configured learning rate
+        // Adam adapts learning rate per parameter
+        let opt = nn::Adam::default().build(&vs, config.learning_rate).unwrap();
+
+        Self {
+            vs,
+            model,
+            opt,
+            config,
+            model_path: model_path.to_string(),
+        }
+    }
+
+    // Run the full training loop with early stopping and best-checkpoint saving
+    // Returns a history of per-epoch stats for the caller to plot or log
+    pub fn fit(
+        &mut self,
+        train_features: &Tensor,
+        train_labels: &Tensor,
+        val_features: &Tensor,
+        val_labels: &Tensor,
+    ) -> TrainingHistory {
+        let device = self.vs.device();
+
+        // Calculate number of batches per epoch
+        // Integer division for complete batches only
+        let n_batches = train_features.size()[0] / self.config.batch_size;
+
+        // Move data to computation device
+        // Ensures CPU/GPU consistency
+        let train_features = train_features.to_device(device);
+        let train_labels = train_labels.to_device(device);
+        let val_features = val_features.to_device(device);
+        let val_labels = val_labels.to_device(device);
+
+        let mut history = TrainingHistory {
+            epochs: Vec::with_capacity(self.config.n_epochs as usize),
+            best_epoch: 0,
+            best_val_loss: f64::INFINITY,
+            best_val_rmse: f64::INFINITY,
+            stopped_early: false,
+        };
+
+        // Tracks how many consecutive validation passes failed to improve
+        // Reset to zero whenever validation loss hits a new best
+        let mut checks_without_improvement = 0;
+
+        // Main training loop
+        // Iterate through all epochs
+        for epoch in 1..=self.config.n_epochs {
+            // Accumulate loss for epoch statistics
+            let mut epoch_loss = 0.0;
+
+            // Mini-batch training loop
+            // Process data in small chunks for efficiency
+            for batch_idx in 0..n_batches {
+                // Calculate batch start and end indices
+                // Ensures we don't exceed data bounds
+                let start = batch_idx * self.config.batch_size;
+                let end = ((batch_idx + 1) * self.config.batch_size).min(train_features.size()[0]);
+
+                // Extract batch of features
+                // narrow creates a view without copying
+                let batch_features = train_features.narrow(0, start, end - start);
+                let batch_labels = train_labels.narrow(0, start, end - start);
+
+                // Forward pass: compute predictions
+                // Model processes batch of inputs
+                let predictions = self.model.forward(&batch_features);
+
+                // Compute mean squared error loss
+                // Measures prediction accuracy
+                let loss = predictions.mse_loss(&batch_labels, tch::Reduction::Mean);
+
+                // Backward pass: compute gradients
+                // Updates all parameters based on loss
+                self.opt.backward_step(&loss);
+
+                // Accumulate batch loss
+                // Convert tensor to f64 for statistics
+                epoch_loss += f64::from(&loss);
+            }
+
+            // Compute average epoch loss
+            // Normalizes by number of batches
+            epoch_loss /= n_batches as f64;
+
+            let mut val_loss = None;
+            let mut val_metrics = None;
+
+            // Validation pass every `validate_every` epochs
+            // Monitors model performance on unseen data
+            if epoch % self.config.validate_every == 0 {
+                // Disable gradient computation for validation
+                // Saves memory and computation
+                let val_predictions = tch::no_grad(|| self.model.forward(&val_features));
+                let metrics = compute_metrics(&val_predictions, &val_labels);
+                let val_loss_value = metrics.rmse * metrics.rmse;
+
+                // Print training progress with colors
+                // Green for good progress, yellow for warnings
+                println!(
+                    "Epoch {:3}/{}: {} {:.4}, {} {:.4} (MAE {:.4}, R2 {:.4})",
+                    epoch,
+                    self.config.n_epochs,
+                    Yellow.paint("Train Loss:"),
+                    epoch_loss,
+                    Green.paint("Val Loss:"),
+                    val_loss_value,
+                    metrics.mae,
+                    metrics.r2,
+                );
+
+                // Best-checkpoint saving: persist to disk whenever validation
+                // loss improves, not just once at the end of training
+                if val_loss_value < history.best_val_loss {
+                    history.best_val_loss = val_loss_value;
+                    history.best_val_rmse = metrics.rmse;
+                    history.best_epoch = epoch;
+                    checks_without_improvement = 0;
+                    self.vs.save(&self.model_path).unwrap();
+                } else {
+                    checks_without_improvement += 1;
+                    if val_loss_value > epoch_loss * 1.5 {
+                        println!("{}", Red.paint("Warning: Possible overfitting detected"));
+                    }
+                }
+
+                val_loss = Some(val_loss_value);
+                val_metrics = Some(metrics);
+            }
+
+            history.epochs.push(EpochRecord {
+                epoch,
+                train_loss: epoch_loss,
+                val_loss,
+                val_metrics,
+            });
+
+            // Real early stopping: once validation loss has failed to improve
+            // for `patience` consecutive checks, stop and restore the best checkpoint
+            if checks_without_improvement >= self.config.patience {
+                println!(
+                    "{}",
+                    Yellow.paint(format!(
+                        "Early stopping at epoch {}: no improvement for {} validation checks",
+                        epoch, self.config.patience
+                    ))
+                );
+                history.stopped_early = true;
+                break;
+            }
+        }
+
+        // Restore the best checkpoint seen during training, since the final
+        // epoch's weights are not necessarily the best ones. Only a run that
+        // actually hit a validation pass ever calls `vs.save`, so skip the
+        // restore when `n_epochs < validate_every` left no checkpoint on disk
+        if history.best_epoch > 0 {
+            self.vs.load(&self.model_path).unwrap();
+            println!(
+                "{}",
+                Green.bold().paint(format!(
+                    "Restored best checkpoint from epoch {} (val loss {:.4}), saved to {}",
+                    history.best_epoch, history.best_val_loss, self.model_path
+                ))
+            );
+        } else {
+            println!(
+                "{}",
+                Yellow.paint("No validation pass ran, so no checkpoint was saved or restored")
+            );
+        }
+
+        history
+    }
+
+    // Consume the learner and hand back its variable store
+    // Used after `fit` to obtain the trained weights for inference
+    pub fn into_var_store(self) -> nn::VarStore {
+        self.vs
+    }
+}
+
+// Compute MAE, RMSE, and R2 between predictions and labels
+// Predictions come out of the model as [n, 1]; labels are [n]
+fn compute_metrics(predictions: &Tensor, labels: &Tensor) -> RegressionMetrics {
+    let predictions = predictions.view([-1]);
+    let diff = &predictions - labels;
+
+    let mae = f64::from(diff.abs().mean(Kind::Float));
+    let mse = f64::from(diff.pow_tensor_scalar(2).mean(Kind::Float));
+    let rmse = mse.sqrt();
+
+    let label_mean = labels.mean(Kind::Float);
+    let ss_tot = f64::from((labels - &label_mean).pow_tensor_scalar(2).sum(Kind::Float));
+    let ss_res = f64::from(diff.pow_tensor_scalar(2).sum(Kind::Float));
+    let r2 = 1.0 - ss_res / ss_tot;
+
+    RegressionMetrics { mae, rmse, r2 }
+}
 
 // Main training function
-// Takes data, device, and model path, returns trained variable store
+// Thin wrapper over `Learner` kept for backward compatibility with callers
+// that just want a trained variable store with default hyperparameters
 pub fn train_model(
     train_features: &Tensor,
     train_labels: &Tensor,
@@ -15,112 +303,59 @@ pub fn train_model(
     device: Device,
     model_path: &str,
 ) -> nn::VarStore {
-    // Create variable store to hold model parameters
-    // This manages all trainable weights and biases
-    let mut vs = nn::VarStore::new(device);
-    
-    // Initialize the model with the variable store
-    // Creates layers and registers parameters
-    let model = GasPriceNet::new(&vs.root());
-    
-    // Create Adam optimizer with learning rate 0.001
-    // Adam adapts learning rate per parameter
-    let mut opt = nn::Adam::default().build(&vs, 1e-3).unwrap();
-    
-    // Training hyperparameters
-    // Number of complete passes through the dataset
-    let n_epochs = 100;
-    // Number of samples per gradient update
-    // Smaller batch = noisier but more frequent updates
-    let batch_size = 32;
-    
-    // Calculate number of batches per epoch
-    // Integer division for complete batches only
-    let n_batches = train_features.size()[0] / batch_size;
-    
-    // Move data to computation device
-    // Ensures CPU/GPU consistency
-    let train_features = train_features.to_device(device);
-    let train_labels = train_labels.to_device(device);
-    let val_features = val_features.to_device(device);
-    let val_labels = val_labels.to_device(device);
-    
-    // Main training loop
-    // Iterate through all epochs
+    let mut learner = Learner::new(device, model_path, ModelConfig::default(), LearnerConfig::default());
+    learner.fit(train_features, train_labels, val_features, val_labels);
+    learner.into_var_store()
+}
+
+// Plain training loop for the recurrent `GasPriceSeqNet`
+// Kept separate from `Learner`, which is specialized to `GasPriceNet`'s
+// feedforward shape and checkpointing; the sequence model only needs enough
+// training to make the multi-step forecast demo meaningful, not the full
+// early-stopping/checkpointing machinery
+pub fn train_seq_model(
+    train_features: &Tensor,
+    train_labels: &Tensor,
+    device: Device,
+    n_epochs: i64,
+    batch_size: i64,
+    learning_rate: f64,
+) -> nn::VarStore {
+    let vs = nn::VarStore::new(device);
+    let model = GasPriceSeqNet::new(&vs.root());
+    let mut opt = nn::Adam::default().build(&vs, learning_rate).unwrap();
+
+    let n_samples = train_features.size()[0];
+    let n_batches = (n_samples / batch_size).max(1);
+
     for epoch in 1..=n_epochs {
-        // Accumulate loss for epoch statistics
         let mut epoch_loss = 0.0;
-        
-        // Mini-batch training loop
-        // Process data in small chunks for efficiency
+
         for batch_idx in 0..n_batches {
-            // Calculate batch start and end indices
-            // Ensures we don't exceed data bounds
             let start = batch_idx * batch_size;
-            let end = ((batch_idx + 1) * batch_size).min(train_features.size()[0]);
-            
-            // Extract batch of features
-            // narrow creates a view without copying
+            let end = ((batch_idx + 1) * batch_size).min(n_samples);
+
             let batch_features = train_features.narrow(0, start, end - start);
             let batch_labels = train_labels.narrow(0, start, end - start);
-            
-            // Forward pass: compute predictions
-            // Model processes batch of inputs
-            let predictions = model.forward(&batch_features);
-            
-            // Compute mean squared error loss
-            // Measures prediction accuracy
+
+            let predictions = model.forward(&batch_features).view([-1]);
             let loss = predictions.mse_loss(&batch_labels, tch::Reduction::Mean);
-            
-            // Backward pass: compute gradients
-            // Updates all parameters based on loss
             opt.backward_step(&loss);
-            
-            // Accumulate batch loss
-            // Convert tensor to f64 for statistics
+
             epoch_loss += f64::from(&loss);
         }
-        
-        // Compute average epoch loss
-        // Normalizes by number of batches
         epoch_loss /= n_batches as f64;
-        
-        // Validation pass every 10 epochs
-        // Monitors model performance on unseen data
-        if epoch % 10 == 0 {
-            // Disable gradient computation for validation
-            // Saves memory and computation
-            let val_predictions = tch::no_grad(|| model.forward(&val_features));
-            // Compute validation loss
-            let val_loss = val_predictions.mse_loss(&val_labels, tch::Reduction::Mean);
-            let val_loss_value = f64::from(&val_loss);
-            
-            // Print training progress with colors
-            // Green for good progress, yellow for warnings
+
+        if epoch % 10 == 0 || epoch == n_epochs {
             println!(
-                "Epoch {:3}/{}: {} {:.4}, {} {:.4}",
-                epoch,
-                n_epochs,
-                Yellow.paint("Train Loss:"),
-                epoch_loss,
-                Green.paint("Val Loss:"),
-                val_loss_value
+                "{}",
+                Yellow.paint(format!(
+                    "  Seq epoch {:3}/{}: train loss {:.4}",
+                    epoch, n_epochs, epoch_loss
+                ))
             );
-            
-            // Early stopping check
-            // Prevents overfitting when validation stops improving
-            if val_loss_value > epoch_loss * 1.5 {
-                println!("{}", Red.paint("Warning: Possible overfitting detected"));
-            }
         }
     }
-    
-    // Save trained model to disk
-    // Allows loading for inference later
-    vs.save(model_path).unwrap();
-    println!("{}", Green.bold().paint(format!("Model saved to {}", model_path)));
-    
-    // Return the variable store
-    // Contains all trained parameters
+
     vs
-}
\ No newline at end of file
+}
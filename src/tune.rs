@@ -0,0 +1,162 @@
+// Import the model/training types the tuner searches over
+use crate::model::ModelConfig;
+use crate::train::{Learner, LearnerConfig};
+// Import random number generation utilities
+use rand::Rng;
+// Import PyTorch components needed to run trial training loops
+use tch::{Device, Tensor};
+
+// Describes the hyperparameter choices the search explores
+// Grid search enumerates every combination; random search samples randomly from these
+pub struct SearchSpace {
+    // Candidate widths for each hidden layer
+    pub hidden_size_choices: Vec<i64>,
+    // Candidate hidden-layer counts (all layers share a width drawn from `hidden_size_choices`)
+    pub n_hidden_layers_choices: Vec<usize>,
+    // Candidate Adam learning rates
+    pub learning_rate_choices: Vec<f64>,
+    // Candidate mini-batch sizes
+    pub batch_size_choices: Vec<i64>,
+}
+
+// A single trial's configuration and the validation RMSE it achieved
+pub struct Trial {
+    pub model_config: ModelConfig,
+    pub learning_rate: f64,
+    pub batch_size: i64,
+    pub val_rmse: f64,
+}
+
+// Train one trial configuration for a short, fixed number of epochs and
+// report its validation RMSE. Trial checkpoints are written to `trial_path`
+// rather than the caller's real model path, since the tuner cares about the
+// resulting metric, not the weights of any individual trial
+fn run_trial(
+    train_features: &Tensor,
+    train_labels: &Tensor,
+    val_features: &Tensor,
+    val_labels: &Tensor,
+    device: Device,
+    trial_path: &str,
+    model_config: ModelConfig,
+    learning_rate: f64,
+    batch_size: i64,
+    epochs_per_trial: i64,
+) -> Trial {
+    let learner_config = LearnerConfig {
+        n_epochs: epochs_per_trial,
+        batch_size,
+        learning_rate,
+        // Validate every epoch and use a short patience, since trials are short
+        validate_every: 1,
+        patience: 3,
+    };
+
+    let mut learner = Learner::new(device, trial_path, model_config.clone(), learner_config);
+    let history = learner.fit(train_features, train_labels, val_features, val_labels);
+
+    Trial {
+        model_config,
+        learning_rate,
+        batch_size,
+        val_rmse: history.best_val_rmse,
+    }
+}
+
+// Build every `(hidden_size, n_hidden_layers)` -> `ModelConfig` combination in the search space
+fn model_configs(space: &SearchSpace) -> Vec<ModelConfig> {
+    let mut configs = Vec::new();
+    for &hidden_size in &space.hidden_size_choices {
+        for &n_layers in &space.n_hidden_layers_choices {
+            configs.push(ModelConfig {
+                input_size: 7,
+                hidden_sizes: vec![hidden_size; n_layers],
+                output_size: 1,
+            });
+        }
+    }
+    configs
+}
+
+// Exhaustively try every combination in the search space
+// Returns the trial with the lowest validation RMSE
+pub fn grid_search(
+    train_features: &Tensor,
+    train_labels: &Tensor,
+    val_features: &Tensor,
+    val_labels: &Tensor,
+    device: Device,
+    space: &SearchSpace,
+    epochs_per_trial: i64,
+) -> Trial {
+    let trial_path = "tune_trial.pt";
+    let mut best: Option<Trial> = None;
+
+    for model_config in model_configs(space) {
+        for &learning_rate in &space.learning_rate_choices {
+            for &batch_size in &space.batch_size_choices {
+                let trial = run_trial(
+                    train_features,
+                    train_labels,
+                    val_features,
+                    val_labels,
+                    device,
+                    trial_path,
+                    model_config.clone(),
+                    learning_rate,
+                    batch_size,
+                    epochs_per_trial,
+                );
+
+                if best.as_ref().map_or(true, |b| trial.val_rmse < b.val_rmse) {
+                    best = Some(trial);
+                }
+            }
+        }
+    }
+
+    best.expect("search space must contain at least one combination")
+}
+
+// Randomly sample `n_trials` combinations from the search space
+// Returns the trial with the lowest validation RMSE
+pub fn random_search(
+    train_features: &Tensor,
+    train_labels: &Tensor,
+    val_features: &Tensor,
+    val_labels: &Tensor,
+    device: Device,
+    space: &SearchSpace,
+    n_trials: usize,
+    epochs_per_trial: i64,
+) -> Trial {
+    let trial_path = "tune_trial.pt";
+    let configs = model_configs(space);
+    let mut rng = rand::thread_rng();
+    let mut best: Option<Trial> = None;
+
+    for _ in 0..n_trials {
+        let model_config = configs[rng.gen_range(0..configs.len())].clone();
+        let learning_rate = space.learning_rate_choices[rng.gen_range(0..space.learning_rate_choices.len())];
+        let batch_size = space.batch_size_choices[rng.gen_range(0..space.batch_size_choices.len())];
+
+        let trial = run_trial(
+            train_features,
+            train_labels,
+            val_features,
+            val_labels,
+            device,
+            trial_path,
+            model_config,
+            learning_rate,
+            batch_size,
+            epochs_per_trial,
+        );
+
+        if best.as_ref().map_or(true, |b| trial.val_rmse < b.val_rmse) {
+            best = Some(trial);
+        }
+    }
+
+    best.expect("n_trials must be greater than zero")
+}